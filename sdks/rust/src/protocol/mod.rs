@@ -21,6 +21,11 @@ use std::error::Error;
 #[cfg(not(target_arch = "wasm32"))]
 use transact::signing::{hash::HashSigner, Signer};
 
+#[cfg(target_arch = "wasm32")]
+use sha2::{Digest, Sha512};
+
+use bech32::{FromBase32, ToBase32};
+
 pub const ADMINISTRATORS_SETTING_ADDRESS: &str =
     "000000a87cb5eafdcca6a814e4add97c4b517d3c530c2f44b31d18e3b0c44298fc1c14";
 pub const ADMINISTRATORS_SETTING_KEY: &str = "sawtooth.swa.administrators";
@@ -32,13 +37,54 @@ pub const SMART_PERMISSION_ADDRESS_PREFIX: &str = "00ec03";
 pub const AGENT_ADDRESS_PREFIX: &str = "cad11d00";
 pub const ORG_ADDRESS_PREFIX: &str = "cad11d01";
 
+/// The bech32 human-readable part used for Sabre state addresses.
+pub const ADDRESS_BECH32_HRP: &str = "sabre";
+
+/// A hashing backend for computing state addresses.
+///
+/// This decouples addressing from `transact::signing`, which is both an
+/// unnecessary abstraction cost for a simple SHA-512 digest and the reason
+/// the `compute_*` functions could not previously compile on `wasm32`. It
+/// also lets tests and benchmarks inject a hasher without pulling in the
+/// whole signing stack.
+trait AddressHasher {
+    fn hash(&self, data: &[u8]) -> Result<Vec<u8>, AddressingError>;
+}
+
+/// The default [`AddressHasher`], backed by `transact`'s `HashSigner` off
+/// the `wasm32` target and by the `sha2` crate on it.
+#[derive(Default)]
+struct DefaultHasher;
+
+#[cfg(not(target_arch = "wasm32"))]
+impl AddressHasher for DefaultHasher {
+    fn hash(&self, data: &[u8]) -> Result<Vec<u8>, AddressingError> {
+        HashSigner::default()
+            .sign(data)
+            .map_err(|err| AddressingError::HashError(format!("failed to hash data: {}", err)))
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl AddressHasher for DefaultHasher {
+    fn hash(&self, data: &[u8]) -> Result<Vec<u8>, AddressingError> {
+        Ok(Sha512::digest(data).to_vec())
+    }
+}
+
 /// Compute a state address for a given namespace registry.
 ///
 /// # Arguments
 ///
 /// * `namespace` - the address prefix for this namespace
-#[cfg(not(target_arch = "wasm32"))]
 pub fn compute_namespace_registry_address(namespace: &str) -> Result<Vec<u8>, AddressingError> {
+    compute_namespace_registry_address_with_hasher(namespace, &DefaultHasher)
+}
+
+fn compute_namespace_registry_address_with_hasher(
+    namespace: &str,
+    hasher: &impl AddressHasher,
+) -> Result<Vec<u8>, AddressingError> {
     let prefix = match namespace.get(..6) {
         Some(x) => x,
         None => {
@@ -48,14 +94,7 @@ pub fn compute_namespace_registry_address(namespace: &str) -> Result<Vec<u8>, Ad
             )));
         }
     };
-    let hash = HashSigner::default()
-        .sign(prefix.as_bytes())
-        .map_err(|err| {
-            AddressingError::HashError(format!(
-                "failed to hash namespace registry address: {}",
-                err
-            ))
-        })?;
+    let hash = hasher.hash(prefix.as_bytes())?;
     Ok([&parse_hex(NAMESPACE_REGISTRY_ADDRESS_PREFIX)?, &hash[..64]].concat())
 }
 
@@ -64,11 +103,15 @@ pub fn compute_namespace_registry_address(namespace: &str) -> Result<Vec<u8>, Ad
 /// # Arguments
 ///
 /// * `name` - the name of the contract registry
-#[cfg(not(target_arch = "wasm32"))]
 pub fn compute_contract_registry_address(name: &str) -> Result<Vec<u8>, AddressingError> {
-    let hash = HashSigner::default().sign(name.as_bytes()).map_err(|err| {
-        AddressingError::HashError(format!("failed to hash contract registry address: {}", err,))
-    })?;
+    compute_contract_registry_address_with_hasher(name, &DefaultHasher)
+}
+
+fn compute_contract_registry_address_with_hasher(
+    name: &str,
+    hasher: &impl AddressHasher,
+) -> Result<Vec<u8>, AddressingError> {
+    let hash = hasher.hash(name.as_bytes())?;
     Ok([&parse_hex(CONTRACT_REGISTRY_ADDRESS_PREFIX)?, &hash[..64]].concat())
 }
 
@@ -78,12 +121,17 @@ pub fn compute_contract_registry_address(name: &str) -> Result<Vec<u8>, Addressi
 ///
 /// * `name` - the name of the contract
 /// * `version` - the version of the contract
-#[cfg(not(target_arch = "wasm32"))]
 pub fn compute_contract_address(name: &str, version: &str) -> Result<Vec<u8>, AddressingError> {
+    compute_contract_address_with_hasher(name, version, &DefaultHasher)
+}
+
+fn compute_contract_address_with_hasher(
+    name: &str,
+    version: &str,
+    hasher: &impl AddressHasher,
+) -> Result<Vec<u8>, AddressingError> {
     let s = String::from(name) + "," + version;
-    let hash = HashSigner::default().sign(s.as_bytes()).map_err(|err| {
-        AddressingError::HashError(format!("failed to hash contract address: {}", err))
-    })?;
+    let hash = hasher.hash(s.as_bytes())?;
     Ok([&parse_hex(CONTRACT_ADDRESS_PREFIX)?, &hash[..64]].concat())
 }
 
@@ -93,18 +141,20 @@ pub fn compute_contract_address(name: &str, version: &str) -> Result<Vec<u8>, Ad
 ///
 /// * `org_id` - the organization's id
 /// * `name` - smart permission name
-#[cfg(not(target_arch = "wasm32"))]
 pub fn compute_smart_permission_address(
     org_id: &str,
     name: &str,
 ) -> Result<Vec<u8>, AddressingError> {
-    let signer = HashSigner::default();
-    let org_id_hash = signer.sign(org_id.as_bytes()).map_err(|err| {
-        AddressingError::HashError(format!("failed to hash pike org id: {}", err))
-    })?;
-    let name_hash = signer.sign(name.as_bytes()).map_err(|err| {
-        AddressingError::HashError(format!("failed to hash smart permission name: {}", err))
-    })?;
+    compute_smart_permission_address_with_hasher(org_id, name, &DefaultHasher)
+}
+
+fn compute_smart_permission_address_with_hasher(
+    org_id: &str,
+    name: &str,
+    hasher: &impl AddressHasher,
+) -> Result<Vec<u8>, AddressingError> {
+    let org_id_hash = hasher.hash(org_id.as_bytes())?;
+    let name_hash = hasher.hash(name.as_bytes())?;
     Ok([
         &parse_hex(SMART_PERMISSION_ADDRESS_PREFIX)?,
         &org_id_hash[..6],
@@ -118,11 +168,15 @@ pub fn compute_smart_permission_address(
 /// # Arguments
 ///
 /// * `name` - the agent's name
-#[cfg(not(target_arch = "wasm32"))]
 pub fn compute_agent_address(name: &[u8]) -> Result<Vec<u8>, AddressingError> {
-    let hash = HashSigner::default().sign(name).map_err(|err| {
-        AddressingError::HashError(format!("failed to hash pike agent address: {}", err))
-    })?;
+    compute_agent_address_with_hasher(name, &DefaultHasher)
+}
+
+fn compute_agent_address_with_hasher(
+    name: &[u8],
+    hasher: &impl AddressHasher,
+) -> Result<Vec<u8>, AddressingError> {
+    let hash = hasher.hash(name)?;
     Ok([&parse_hex(AGENT_ADDRESS_PREFIX)?, &hash[..62]].concat())
 }
 
@@ -131,11 +185,15 @@ pub fn compute_agent_address(name: &[u8]) -> Result<Vec<u8>, AddressingError> {
 /// # Arguments
 ///
 /// * `id` - the organization's id
-#[cfg(not(target_arch = "wasm32"))]
 pub fn compute_org_address(id: &str) -> Result<Vec<u8>, AddressingError> {
-    let hash = HashSigner::default().sign(id.as_bytes()).map_err(|err| {
-        AddressingError::HashError(format!("failed to hash pike org address: {}", err))
-    })?;
+    compute_org_address_with_hasher(id, &DefaultHasher)
+}
+
+fn compute_org_address_with_hasher(
+    id: &str,
+    hasher: &impl AddressHasher,
+) -> Result<Vec<u8>, AddressingError> {
+    let hash = hasher.hash(id.as_bytes())?;
     Ok([&parse_hex(ORG_ADDRESS_PREFIX)?, &hash[..62]].concat())
 }
 
@@ -158,10 +216,200 @@ fn parse_hex(hex: &str) -> Result<Vec<u8>, AddressingError> {
     Ok(res)
 }
 
+/// Convert bytes to a hex string.
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Decodes a hex string, with an optional `0x` prefix, into a fixed-size
+/// byte array.
+///
+/// Unlike [`parse_hex`], this fails fast if the input (after stripping the
+/// prefix) is not exactly `2 * N` hex characters, rather than accepting
+/// whatever length happens to be passed in. Operates on raw bytes rather
+/// than `&str` indices so malformed (e.g. non-ASCII) input is rejected
+/// with an `Err` instead of panicking on a non-char-boundary slice.
+fn decode_hex_fixed<const N: usize>(input: &str) -> Result<[u8; N], AddressingError> {
+    let stripped = input.strip_prefix("0x").unwrap_or(input).as_bytes();
+
+    if stripped.len() % 2 != 0 {
+        return Err(AddressingError::InvalidInput(format!(
+            "hex string has odd number of digits: {}",
+            input
+        )));
+    }
+
+    if stripped.len() != N * 2 {
+        return Err(AddressingError::InvalidInput(format!(
+            "hex string has invalid length: expected {} characters, got {}",
+            N * 2,
+            stripped.len()
+        )));
+    }
+
+    let mut bytes = [0u8; N];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        let hi = hex_digit(stripped[i * 2], input)?;
+        let lo = hex_digit(stripped[i * 2 + 1], input)?;
+        *byte = (hi << 4) | lo;
+    }
+
+    Ok(bytes)
+}
+
+/// Converts a single ASCII hex digit byte into its numeric value.
+fn hex_digit(digit: u8, input: &str) -> Result<u8, AddressingError> {
+    (digit as char)
+        .to_digit(16)
+        .map(|d| d as u8)
+        .ok_or_else(|| {
+            AddressingError::InvalidInput(format!("string contains invalid hex: {}", input))
+        })
+}
+
+/// Parses a user-supplied state address string into the canonical 35-byte
+/// form.
+///
+/// # Arguments
+///
+/// * `input` - the hex-encoded state address, optionally prefixed with `0x`
+pub fn parse_state_address(input: &str) -> Result<[u8; 35], AddressingError> {
+    decode_hex_fixed::<35>(input)
+}
+
+/// The kind of Sabre object a state address refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressKind {
+    NamespaceRegistry,
+    ContractRegistry,
+    Contract,
+    SmartPermission,
+    Agent,
+    Org,
+}
+
+/// A Sabre state address that has been classified by its address prefix.
+///
+/// This allows code that scans global state to determine which kind of
+/// Sabre object an arbitrary 35-byte address refers to, without having to
+/// hardcode prefix comparisons against
+/// [`NAMESPACE_REGISTRY_ADDRESS_PREFIX`] and friends.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Address {
+    NamespaceRegistry(Vec<u8>),
+    ContractRegistry(Vec<u8>),
+    Contract(Vec<u8>),
+    SmartPermission(Vec<u8>),
+    Agent(Vec<u8>),
+    Org(Vec<u8>),
+}
+
+impl Address {
+    /// Returns the kind of Sabre object this address refers to.
+    pub fn kind(&self) -> AddressKind {
+        match self {
+            Address::NamespaceRegistry(_) => AddressKind::NamespaceRegistry,
+            Address::ContractRegistry(_) => AddressKind::ContractRegistry,
+            Address::Contract(_) => AddressKind::Contract,
+            Address::SmartPermission(_) => AddressKind::SmartPermission,
+            Address::Agent(_) => AddressKind::Agent,
+            Address::Org(_) => AddressKind::Org,
+        }
+    }
+
+    /// Returns the raw bytes of this address.
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            Address::NamespaceRegistry(bytes) => bytes,
+            Address::ContractRegistry(bytes) => bytes,
+            Address::Contract(bytes) => bytes,
+            Address::SmartPermission(bytes) => bytes,
+            Address::Agent(bytes) => bytes,
+            Address::Org(bytes) => bytes,
+        }
+    }
+
+    /// Encodes this address as a checksummed bech32 string, e.g.
+    /// `sabre1...`, that is safe to copy-paste into CLI commands or logs.
+    pub fn to_bech32(&self) -> Result<String, AddressingError> {
+        bech32::encode(ADDRESS_BECH32_HRP, self.as_bytes().to_base32()).map_err(|err| {
+            AddressingError::InvalidInput(format!("failed to encode address as bech32: {}", err))
+        })
+    }
+
+    /// Decodes a checksummed bech32 address produced by [`Address::to_bech32`].
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - the bech32-encoded address
+    pub fn from_bech32(s: &str) -> Result<Address, AddressingError> {
+        let (hrp, data) = bech32::decode(s).map_err(|err| {
+            AddressingError::InvalidInput(format!("failed to decode bech32 address: {}", err))
+        })?;
+        if hrp != ADDRESS_BECH32_HRP {
+            return Err(AddressingError::InvalidInput(format!(
+                "bech32 address has unexpected human-readable part: expected '{}', got '{}'",
+                ADDRESS_BECH32_HRP, hrp
+            )));
+        }
+        let bytes = Vec::<u8>::from_base32(&data).map_err(|err| {
+            AddressingError::InvalidInput(format!("failed to decode bech32 payload: {}", err))
+        })?;
+        if bytes.len() != 35 {
+            return Err(AddressingError::InvalidInput(format!(
+                "decoded bech32 address has invalid length: expected 35 bytes, got {}",
+                bytes.len()
+            )));
+        }
+        Address::try_from_bytes(&bytes)
+    }
+
+    /// Classifies a raw state address by its prefix.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - the raw state address
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Address, AddressingError> {
+        if bytes.len() != 35 {
+            return Err(AddressingError::InvalidInput(format!(
+                "state address has invalid length: expected 35 bytes, got {}",
+                bytes.len()
+            )));
+        }
+
+        let hex = to_hex(bytes);
+        if hex.starts_with(NAMESPACE_REGISTRY_ADDRESS_PREFIX) {
+            Ok(Address::NamespaceRegistry(bytes.to_vec()))
+        } else if hex.starts_with(CONTRACT_REGISTRY_ADDRESS_PREFIX) {
+            Ok(Address::ContractRegistry(bytes.to_vec()))
+        } else if hex.starts_with(CONTRACT_ADDRESS_PREFIX) {
+            Ok(Address::Contract(bytes.to_vec()))
+        } else if hex.starts_with(SMART_PERMISSION_ADDRESS_PREFIX) {
+            Ok(Address::SmartPermission(bytes.to_vec()))
+        } else if hex.starts_with(AGENT_ADDRESS_PREFIX) {
+            Ok(Address::Agent(bytes.to_vec()))
+        } else if hex.starts_with(ORG_ADDRESS_PREFIX) {
+            Ok(Address::Org(bytes.to_vec()))
+        } else {
+            Err(AddressingError::UnknownPrefix(hex))
+        }
+    }
+}
+
+impl std::str::FromStr for Address {
+    type Err = AddressingError;
+
+    /// Parses a hex-encoded state address, classifying it by its prefix.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Address::try_from_bytes(&parse_state_address(s)?)
+    }
+}
+
 #[derive(Debug)]
 pub enum AddressingError {
     HashError(String),
     InvalidInput(String),
+    UnknownPrefix(String),
 }
 
 impl Error for AddressingError {}
@@ -171,6 +419,142 @@ impl std::fmt::Display for AddressingError {
         match self {
             AddressingError::HashError(msg) => write!(f, "failed to produce hash: {}", msg),
             AddressingError::InvalidInput(msg) => write!(f, "addressing input is invalid: {}", msg),
+            AddressingError::UnknownPrefix(hex) => {
+                write!(f, "address has unrecognized prefix: {}", hex)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a fake state address of `total_len` bytes with the given hex
+    /// prefix, zero-filled after the prefix.
+    fn sample_address(prefix: &str, total_len: usize) -> Vec<u8> {
+        let mut bytes = parse_hex(prefix).expect("prefix is valid hex");
+        bytes.resize(total_len, 0);
+        bytes
+    }
+
+    #[test]
+    fn decode_hex_fixed_rejects_short_input() {
+        assert!(matches!(
+            decode_hex_fixed::<4>("ab"),
+            Err(AddressingError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn decode_hex_fixed_rejects_odd_length_input() {
+        assert!(matches!(
+            decode_hex_fixed::<4>("abc"),
+            Err(AddressingError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn decode_hex_fixed_rejects_over_length_input() {
+        assert!(matches!(
+            decode_hex_fixed::<2>("aabbcc"),
+            Err(AddressingError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn decode_hex_fixed_rejects_non_ascii_input_without_panicking() {
+        // Byte length is 4, matching N * 2 for N = 2, so this previously
+        // reached the `&str` slicing and panicked on a non-char-boundary
+        // index instead of returning an error.
+        assert!(matches!(
+            decode_hex_fixed::<2>("€3"),
+            Err(AddressingError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn decode_hex_fixed_strips_0x_prefix() {
+        assert_eq!(decode_hex_fixed::<2>("0xabcd").unwrap(), [0xab, 0xcd]);
+    }
+
+    #[test]
+    fn parse_state_address_accepts_exact_length() {
+        let hex: String = "ab".repeat(35);
+        assert_eq!(parse_state_address(&hex).unwrap(), [0xab; 35]);
+    }
+
+    #[test]
+    fn parse_state_address_rejects_wrong_length() {
+        let hex: String = "ab".repeat(34);
+        assert!(parse_state_address(&hex).is_err());
+    }
+
+    #[test]
+    fn try_from_bytes_rejects_wrong_length() {
+        let bytes = sample_address(NAMESPACE_REGISTRY_ADDRESS_PREFIX, 10);
+        assert!(matches!(
+            Address::try_from_bytes(&bytes),
+            Err(AddressingError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn try_from_bytes_classifies_known_prefixes() {
+        let cases = [
+            (NAMESPACE_REGISTRY_ADDRESS_PREFIX, AddressKind::NamespaceRegistry),
+            (CONTRACT_REGISTRY_ADDRESS_PREFIX, AddressKind::ContractRegistry),
+            (CONTRACT_ADDRESS_PREFIX, AddressKind::Contract),
+            (SMART_PERMISSION_ADDRESS_PREFIX, AddressKind::SmartPermission),
+            (AGENT_ADDRESS_PREFIX, AddressKind::Agent),
+            (ORG_ADDRESS_PREFIX, AddressKind::Org),
+        ];
+        for (prefix, expected_kind) in cases {
+            let address = Address::try_from_bytes(&sample_address(prefix, 35)).unwrap();
+            assert_eq!(address.kind(), expected_kind);
         }
     }
+
+    #[test]
+    fn try_from_bytes_rejects_unknown_prefix() {
+        let bytes = sample_address("ffffff", 35);
+        assert!(matches!(
+            Address::try_from_bytes(&bytes),
+            Err(AddressingError::UnknownPrefix(_))
+        ));
+    }
+
+    #[test]
+    fn address_from_str_round_trips_through_hex() {
+        let bytes = sample_address(AGENT_ADDRESS_PREFIX, 35);
+        let address: Address = to_hex(&bytes).parse().unwrap();
+        assert_eq!(address.kind(), AddressKind::Agent);
+        assert_eq!(address.as_bytes(), bytes.as_slice());
+    }
+
+    #[test]
+    fn bech32_round_trips() {
+        let bytes = sample_address(ORG_ADDRESS_PREFIX, 35);
+        let address = Address::try_from_bytes(&bytes).unwrap();
+        let encoded = address.to_bech32().unwrap();
+        assert!(encoded.starts_with(ADDRESS_BECH32_HRP));
+        assert_eq!(Address::from_bech32(&encoded).unwrap(), address);
+    }
+
+    #[test]
+    fn from_bech32_rejects_wrong_hrp() {
+        let bytes = sample_address(ORG_ADDRESS_PREFIX, 35);
+        let encoded = bech32::encode("notsabre", bytes.to_base32()).unwrap();
+        assert!(matches!(
+            Address::from_bech32(&encoded),
+            Err(AddressingError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn default_hasher_is_deterministic() {
+        let hasher = DefaultHasher;
+        assert_eq!(hasher.hash(b"sabre").unwrap(), hasher.hash(b"sabre").unwrap());
+        assert_ne!(hasher.hash(b"sabre").unwrap(), hasher.hash(b"other").unwrap());
+    }
 }